@@ -1,10 +1,17 @@
 mod overloading;
 
-use overloading::{f, f_xor, AsTrait1, AsTrait2, FResult, FXorResult, Trait1, Trait2};
+use overloading::{
+    f, f3, f_bits, f_xor, g, AsTrait1, AsTrait1Guarded, AsTrait2, AsTrait2Guarded, AsTraits,
+    F3Result, FResult, FXorResult, GResult, GuardCond, Trait1, Trait2,
+};
 
 struct MyType1;
 struct MyType2;
 struct MyType3;
+struct MyType4;
+struct MyType5;
+struct MyType6;
+struct MyType7;
 
 impl Trait1 for MyType1 {
     fn method1(&self) -> u32 {
@@ -24,6 +31,48 @@ impl Trait1 for MyType3 {
     }
 }
 
+impl Trait1 for MyType4 {
+    fn method1(&self) -> u32 {
+        11
+    }
+}
+
+impl Trait2 for MyType4 {
+    fn method2(&self) -> String {
+        "both".into()
+    }
+}
+
+impl Trait1 for MyType5 {
+    fn method1(&self) -> u32 {
+        100
+    }
+}
+
+impl GuardCond for MyType5 {
+    const COND: bool = true;
+}
+
+impl Trait1 for MyType6 {
+    fn method1(&self) -> u32 {
+        1
+    }
+}
+
+impl GuardCond for MyType6 {
+    const COND: bool = false;
+}
+
+impl Trait2 for MyType7 {
+    fn method2(&self) -> String {
+        "guarded-str".into()
+    }
+}
+
+impl GuardCond for MyType7 {
+    const COND: bool = true;
+}
+
 fn main() {
     let t1 = MyType1;
     let t2 = MyType2;
@@ -39,5 +88,24 @@ fn main() {
     assert!(f_xor((AsTrait2(&t2), AsTrait1(&t3))) == FXorResult::StrInt("asd".into(), 3));
     // f_xor((AsTrait2(&t2), AsTrait2(&t2)));  // trait bound not satisfied
 
+    assert!(f3((AsTrait1(&t1), AsTrait1(&t3), AsTrait1(&t1))) == F3Result::IntIntInt(7, 3, 7));
+    assert!(f3((AsTrait1(&t1), AsTrait2(&t2), AsTrait1(&t3))) == F3Result::Str("asd".into()));
+
+    let t4 = MyType4;
+    // Both bits set on both sides: the Trait1-pinned arm (specificity 2)
+    // outscores the two Trait2-pinned arms (specificity 1 each), so there's
+    // no ambiguity even though all three arms are applicable.
+    assert!(f_bits(AsTraits!(&t4), AsTraits!(&t4)) == FResult::IntInt(11, 11));
+    assert!(f_bits(AsTrait1(&t1), AsTrait2(&t2)) == FResult::Str("asd".into()));
+    assert!(f_bits(AsTrait1(&t1), AsTraits!(&t4)) == FResult::IntInt(7, 11));
+    // f_bits(AsTraits!(&t4), AsTrait2(&t2));  // ambiguous: ties the two Trait2-pinned arms
+
+    let t5 = MyType5;
+    let t6 = MyType6;
+    let t7 = MyType7;
+    assert!(g(AsTrait1Guarded(&t5)) == GResult::Big(100));
+    assert!(g(AsTrait1Guarded(&t6)) == GResult::Small(1));
+    assert!(g(AsTrait2Guarded(&t7)) == GResult::Str("guarded-str".into()));
+
     println!("All OK!");
 }
@@ -6,62 +6,122 @@ pub trait Trait2 {
     fn method2(&self) -> String;
 }
 
-struct NoImplTrait1 {}
-struct NoImplTrait2 {}
-
-impl Trait1 for NoImplTrait1 {
-    fn method1(&self) -> u32 {
-        unimplemented!()
-    }
-}
+/// Generates the dispatch scaffolding for an overload set: one `AsTraitN`
+/// newtype wrapper per participating trait, a sealed `AsAnyTrait`-style
+/// trait carrying a `const TAG` discriminant plus one accessor per trait,
+/// and the dummy "no-impl" statics that back the accessors a given wrapper
+/// doesn't represent.
+///
+/// The dispatch function itself is *not* generated: it still has to be
+/// written by hand as an exhaustive `match` over the tag(s) exposed by the
+/// sealed trait, exactly like `f` below. What the macro removes is the
+/// boilerplate every overload set needs before that match can be written.
+macro_rules! overload {
+    (
+        tag $tag:ident;
+        sealed $sealed:ident;
+        traits {
+            $(
+                $trait_name:ident {
+                    wrapper: $wrapper:ident,
+                    accessor: $accessor:ident,
+                    dummy_ty: $dummy_ty:ident,
+                    dummy_static: $dummy_static:ident,
+                    dummy_impl { $($dummy_item:item)* }
+                }
+            )+
+        }
+    ) => {
+        enum $tag {
+            $($trait_name),+
+        }
 
-impl Trait2 for NoImplTrait2 {
-    fn method2(&self) -> String {
-        unimplemented!()
-    }
-}
+        $(
+            struct $dummy_ty {}
 
-static NO_IMPL_TRAIT1: NoImplTrait1 = NoImplTrait1 {};
-static NO_IMPL_TRAIT2: NoImplTrait2 = NoImplTrait2 {};
+            impl $trait_name for $dummy_ty {
+                $($dummy_item)*
+            }
 
-#[derive(Clone, Copy)]
-pub struct AsTrait1<'a, T: Trait1>(pub &'a T);
+            static $dummy_static: $dummy_ty = $dummy_ty {};
 
-#[derive(Clone, Copy)]
-pub struct AsTrait2<'a, T: Trait2>(pub &'a T);
+            #[derive(Clone, Copy)]
+            pub struct $wrapper<'a, T: $trait_name>(pub &'a T);
+        )+
 
-enum AllTraits {
-    Trait1,
-    Trait2,
-}
+        trait $sealed {
+            const TAG: $tag;
+            $(
+                fn $accessor(&self) -> &impl $trait_name;
+            )+
+        }
 
-trait AsTrait1Or2 {
-    const TRAIT: AllTraits;
-    fn t1(&self) -> &impl Trait1;
-    fn t2(&self) -> &impl Trait2;
-}
+        overload!(@impls $tag, $sealed; []; $(
+            { trait_name: $trait_name, wrapper: $wrapper, accessor: $accessor, dummy_static: $dummy_static, }
+        )+);
+    };
 
-impl<T: Trait1> AsTrait1Or2 for AsTrait1<'_, T> {
-    const TRAIT: AllTraits = AllTraits::Trait1;
+    // Base case: nothing left to process.
+    (@impls $tag:ident, $sealed:ident; [$($done:tt)*];) => {};
 
-    fn t1(&self) -> &impl Trait1 {
-        self.0
-    }
+    // Peel the current entry off, implementing it against every other
+    // entry (those already `$done` plus those still to come in `$rest`),
+    // then recurse with `$cur` moved into `$done`.
+    (@impls $tag:ident, $sealed:ident; [$($done:tt)*]; $cur:tt $($rest:tt)*) => {
+        overload!(@impl_one $tag, $sealed; $cur; [$($done)* $($rest)*]);
+        overload!(@impls $tag, $sealed; [$($done)* $cur]; $($rest)*);
+    };
 
-    fn t2(&self) -> &impl Trait2 {
-        &NO_IMPL_TRAIT2
-    }
-}
+    (@impl_one $tag:ident, $sealed:ident;
+        {
+            trait_name: $cur_trait:ident,
+            wrapper: $cur_wrapper:ident,
+            accessor: $cur_accessor:ident,
+            dummy_static: $cur_dummy:ident,
+        };
+        [$(
+            {
+                trait_name: $other_trait:ident,
+                wrapper: $other_wrapper:ident,
+                accessor: $other_accessor:ident,
+                dummy_static: $other_dummy:ident,
+            }
+        )*]
+    ) => {
+        impl<T: $cur_trait> $sealed for $cur_wrapper<'_, T> {
+            const TAG: $tag = $tag::$cur_trait;
 
-impl<T: Trait2> AsTrait1Or2 for AsTrait2<'_, T> {
-    const TRAIT: AllTraits = AllTraits::Trait2;
+            fn $cur_accessor(&self) -> &impl $cur_trait {
+                self.0
+            }
 
-    fn t1(&self) -> &impl Trait1 {
-        &NO_IMPL_TRAIT1
-    }
+            $(
+                fn $other_accessor(&self) -> &impl $other_trait {
+                    &$other_dummy
+                }
+            )*
+        }
+    };
+}
 
-    fn t2(&self) -> &impl Trait2 {
-        self.0
+overload! {
+    tag AllTraits;
+    sealed AsTrait1Or2;
+    traits {
+        Trait1 {
+            wrapper: AsTrait1,
+            accessor: t1,
+            dummy_ty: NoImplTrait1,
+            dummy_static: NO_IMPL_TRAIT1,
+            dummy_impl { fn method1(&self) -> u32 { unimplemented!() } }
+        }
+        Trait2 {
+            wrapper: AsTrait2,
+            accessor: t2,
+            dummy_ty: NoImplTrait2,
+            dummy_static: NO_IMPL_TRAIT2,
+            dummy_impl { fn method2(&self) -> String { unimplemented!() } }
+        }
     }
 }
 
@@ -71,14 +131,350 @@ pub enum FResult {
     IntInt(u32, u32),
 }
 
-#[allow(private_bounds)]
-pub fn f<T1: AsTrait1Or2, T2: AsTrait1Or2>(x: T1, y: T2) -> FResult {
-    // NOTE: It's important to ALWAYS check for exhaustiveness.
-    match (T1::TRAIT, T2::TRAIT) {
-        (AllTraits::Trait2, _) => FResult::Str(x.t2().method2()),
-        (_, AllTraits::Trait2) => FResult::Str(y.t2().method2()),
-        (AllTraits::Trait1, AllTraits::Trait1) => FResult::IntInt(
-            x.t1().method1(), y.t1().method1()),
+/// Expands `variants` to the power `positions.len()` -- every tag
+/// combination a dispatch function over that many arguments can see --
+/// and hands the result to `$callback!` as a trailing `combos [ [v, ...],
+/// ... ]` group appended after `$fixed`. `positions` only matters for its
+/// length; `check_overload!` passes the argument-type idents through
+/// unused just to drive the recursion one step per argument.
+macro_rules! cartesian_tags {
+    ($callback:ident ! { $($fixed:tt)* } ; variants [$($variant:ident),+ $(,)?] ; positions [$($pos:tt),+ $(,)?]) => {
+        cartesian_tags!(@grow
+            $callback ! { $($fixed)* } ;
+            variants [$($variant),+] ;
+            acc [ [] ] ;
+            remaining [$($pos),+]
+        );
+    };
+
+    // No more positions left to fill: hand the finished combos to the
+    // callback.
+    (@grow $callback:ident ! { $($fixed:tt)* } ; variants [$($variant:ident),+] ; acc [$($combo:tt),+] ; remaining []) => {
+        $callback!( $($fixed)* combos [ $($combo),+ ] );
+    };
+
+    // One more position to fill: every existing combo turns into
+    // `variants.len()` combos (one per variant appended). Peeling combos
+    // off one at a time via `@extend` keeps each step's repetition a
+    // single, flat list -- trying to cross two repeated lists (combos x
+    // variants) directly in one nested expansion is a rustc error
+    // ("meta-variable ... repeats N times, but ... repeats M times").
+    (@grow $callback:ident ! { $($fixed:tt)* } ; variants [$($variant:ident),+] ; acc [$($combo:tt),+] ; remaining [$pos:tt $(, $rest:tt)*]) => {
+        cartesian_tags!(@extend
+            $callback ! { $($fixed)* } ;
+            variants [$($variant),+] ;
+            remaining [$($rest),*] ;
+            grown [] ;
+            todo [$($combo),+]
+        );
+    };
+
+    // Every combo for this position has been extended; recurse to fill
+    // the next position (or finish, via the `@grow` rule above).
+    (@extend $callback:ident ! { $($fixed:tt)* } ; variants [$($variant:ident),+] ; remaining [$($rest:tt),*] ; grown [$($grown:tt),*] ; todo []) => {
+        cartesian_tags!(@grow
+            $callback ! { $($fixed)* } ;
+            variants [$($variant),+] ;
+            acc [$($grown),*] ;
+            remaining [$($rest),*]
+        );
+    };
+
+    // Peel one combo off `todo`, append every variant to it (via
+    // `@append_variants`, which peels the variants one at a time for the
+    // same reason), and fold the results into `grown`.
+    (@extend $callback:ident ! { $($fixed:tt)* } ; variants [$($variant:ident),+] ; remaining [$($rest:tt),*] ; grown [$($grown:tt),*] ; todo [[$($combo:ident),*] $(, $todo_rest:tt)*]) => {
+        cartesian_tags!(@append_variants
+            $callback ! { $($fixed)* } ;
+            variants [$($variant),+] ;
+            remaining [$($rest),*] ;
+            grown [$($grown),*] ;
+            todo [$($todo_rest),*] ;
+            combo [$($combo),*] ;
+            new [] ;
+            todo_variants [$($variant),+]
+        );
+    };
+
+    // Done appending variants to the current combo: fold the new combos
+    // into `grown` and move on to the next combo in `todo`.
+    (@append_variants
+        $callback:ident ! { $($fixed:tt)* } ;
+        variants [$($variant:ident),+] ;
+        remaining [$($rest:tt),*] ;
+        grown [$($grown:tt),*] ;
+        todo [$($todo:tt),*] ;
+        combo [$($combo:ident),*] ;
+        new [$($new:tt),*] ;
+        todo_variants []
+    ) => {
+        cartesian_tags!(@extend
+            $callback ! { $($fixed)* } ;
+            variants [$($variant),+] ;
+            remaining [$($rest),*] ;
+            grown [$($grown,)* $($new),*] ;
+            todo [$($todo),*]
+        );
+    };
+
+    // Peel one variant off `todo_variants` and append it to `combo`. Only
+    // `combo` (a single already-peeled combo) is re-expanded via `$()*`
+    // here, never alongside another independently-sized repeated list --
+    // that's what made the all-at-once cross product a rustc error above.
+    (@append_variants
+        $callback:ident ! { $($fixed:tt)* } ;
+        variants [$($variant:ident),+] ;
+        remaining [$($rest:tt),*] ;
+        grown [$($grown:tt),*] ;
+        todo [$($todo:tt),*] ;
+        combo [$($combo:ident),*] ;
+        new [$($new:tt),*] ;
+        todo_variants [$cur_variant:ident $(, $rest_variant:ident)*]
+    ) => {
+        cartesian_tags!(@append_variants
+            $callback ! { $($fixed)* } ;
+            variants [$($variant),+] ;
+            remaining [$($rest),*] ;
+            grown [$($grown),*] ;
+            todo [$($todo),*] ;
+            combo [$($combo),*] ;
+            new [$($new,)* [$($combo,)* $cur_variant]] ;
+            todo_variants [$($rest_variant),*]
+        );
+    };
+}
+
+/// Wraps a dispatch function's match over argument tags with a static
+/// exhaustiveness *and* reachability check: for every tag combination the
+/// arguments' `tag` type can produce, some arm of the match must apply
+/// (exhaustiveness), and every arm must be the first to apply for at
+/// least one combination (reachability -- a duplicated or fully-shadowed
+/// arm is as much a bug here as a missing one). Each violation fails at
+/// compile time via a `const` panic naming the offending combination or
+/// arm, e.g. "no arm covering (x = Trait1, y = Trait1)". A literal
+/// `compile_error!` can't take a macro-computed string, so this uses the
+/// same const-panic-as-diagnostic mechanism as `Selection::resolve` below.
+macro_rules! check_overload {
+    (
+        $(#[$meta:meta])*
+        $vis:vis fn $name:ident<$($arg_ty:ident: $bound:path),+>($($arg:ident: $arg_ty2:ident),+) -> $ret:ty {
+            tag $tag:ident { $($variant:ident),+ $(,)? }
+            match ($($tag_expr:expr),+ $(,)?) {
+                $($pat:pat => $body:expr),+ $(,)?
+            }
+        }
+    ) => {
+        cartesian_tags!(
+            check_overload! {
+                @emit
+                meta [ $(#[$meta])* ] ;
+                vis [$vis] ;
+                name [$name] ;
+                generics [$($arg_ty: $bound),+] ;
+                args [$($arg: $arg_ty2),+] ;
+                arg_names [$($arg),+] ;
+                ret [$ret] ;
+                tag [$tag] ;
+                tag_exprs [$($tag_expr),+] ;
+                arms [$($pat => $body),+] ;
+            } ;
+            variants [$($variant),+] ;
+            positions [$($arg_ty),+]
+        );
+    };
+
+    (@emit
+        meta [ $(#[$meta:meta])* ] ;
+        vis [$vis:vis] ;
+        name [$name:ident] ;
+        generics [$($arg_ty:ident: $bound:path),+] ;
+        args [$($arg:ident: $arg_ty2:ident),+] ;
+        arg_names [$($arg_name:ident),+] ;
+        ret [$ret:ty] ;
+        tag [$tag:ident] ;
+        tag_exprs [$($tag_expr:expr),+] ;
+        arms [$($pat:pat => $body:expr),+] ;
+        combos [$($combo:tt),+]
+    ) => {
+        check_overload!(@assert_all_covered
+            name [$name] ;
+            tag [$tag] ;
+            arg_names [$($arg_name),+] ;
+            arms [$($pat),+] ;
+            combos [$($combo),+]
+        );
+
+        check_overload!(@assert_reachable
+            name [$name] ;
+            tag [$tag] ;
+            combos [$($combo),+] ;
+            seen [] ;
+            rest [ $($pat),+ ]
+        );
+
+        $(#[$meta])*
+        $vis fn $name<$($arg_ty: $bound),+>($($arg: $arg_ty2),+) -> $ret {
+            match ($($tag_expr),+) {
+                $($pat => $body),+
+            }
+        }
+    };
+
+    // Peel combos off one at a time -- `arg_names`/`arms`/`combo` are
+    // three independently-sized flat lists, so folding them into a single
+    // `$(...)` alongside a repeated `combos` would hit the same
+    // repeat-count mismatch `cartesian_tags!` works around above. Each
+    // `@assert_covered` call below instead receives them as separate
+    // complete arguments.
+    (@assert_all_covered
+        name [$name:ident] ;
+        tag [$tag:ident] ;
+        arg_names [$($arg_name:ident),+] ;
+        arms [$($pat:pat),+] ;
+        combos []
+    ) => {};
+
+    (@assert_all_covered
+        name [$name:ident] ;
+        tag [$tag:ident] ;
+        arg_names [$($arg_name:ident),+] ;
+        arms [$($pat:pat),+] ;
+        combos [[$($combo:ident),+] $(, $rest:tt)*]
+    ) => {
+        check_overload!(@assert_covered
+            name [$name] ;
+            tag [$tag] ;
+            arg_names [$($arg_name),+] ;
+            arms [$($pat),+] ;
+            combo [$($combo),+]
+        );
+
+        check_overload!(@assert_all_covered
+            name [$name] ;
+            tag [$tag] ;
+            arg_names [$($arg_name),+] ;
+            arms [$($pat),+] ;
+            combos [$($rest),*]
+        );
+    };
+
+    // Exhaustiveness: every enumerated combination must be matched by at
+    // least one arm.
+    (@assert_covered
+        name [$name:ident] ;
+        tag [$tag:ident] ;
+        arg_names [$($arg_name:ident),+] ;
+        arms [$($pat:pat),+] ;
+        combo [$($combo:ident),+]
+    ) => {
+        const _: () = {
+            if !matches!(($($tag::$combo),+), $($pat)|+) {
+                panic!(concat!(
+                    "check_overload!: `", stringify!($name),
+                    "` has no arm covering (", stringify!($($arg_name = $combo),+), ")"
+                ));
+            }
+        };
+    };
+
+    // Reachability: peel arms off front-to-back, carrying the patterns
+    // `seen` so far. An arm is reachable only if some enumerated
+    // combination matches it but matches none of the earlier arms --
+    // otherwise it's fully shadowed, the match-arm equivalent of a
+    // duplicate or dead branch.
+    (@assert_reachable
+        name [$name:ident] ;
+        tag [$tag:ident] ;
+        combos [$($combo:tt),+] ;
+        seen [$($seen:pat),*] ;
+        rest [ $cur:pat $(, $rest_pat:pat)* ]
+    ) => {
+        const _: () = {
+            if !check_overload!(@reachable_any
+                tag [$tag] ;
+                cur [$cur] ;
+                seen [$($seen),*] ;
+                combos [$($combo),+]
+            ) {
+                panic!(concat!(
+                    "check_overload!: `", stringify!($name),
+                    "` arm `", stringify!($cur),
+                    "` is unreachable -- fully shadowed by earlier arms"
+                ));
+            }
+        };
+
+        check_overload!(@assert_reachable
+            name [$name] ;
+            tag [$tag] ;
+            combos [$($combo),+] ;
+            seen [ $($seen,)* $cur ] ;
+            rest [ $($rest_pat),* ]
+        );
+    };
+
+    (@assert_reachable
+        name [$name:ident] ;
+        tag [$tag:ident] ;
+        combos [$($combo:tt),+] ;
+        seen [$($seen:pat),*] ;
+        rest []
+    ) => {};
+
+    // Whether some combo matches `cur` and matches none of the earlier
+    // arms' patterns. Peeled one combo at a time like `cartesian_tags!`'s
+    // own recursion, rather than folding `combos` (a list of lists) and
+    // `seen` (a flat list) into one shared repetition -- the two have
+    // different repeat depths and rustc rejects mixing them directly.
+    (@reachable_any
+        tag [$tag:ident] ;
+        cur [$cur:pat] ;
+        seen [$($seen:pat),*] ;
+        combos []
+    ) => {
+        false
+    };
+
+    (@reachable_any
+        tag [$tag:ident] ;
+        cur [$cur:pat] ;
+        seen [$($seen:pat),*] ;
+        combos [[$($combo:ident),+] $(, $rest:tt)*]
+    ) => {
+        (matches!(($($tag::$combo),+), $cur) && check_overload!(@not_seen
+            tag [$tag] ;
+            combo [$($combo),+] ;
+            seen [$($seen),*]
+        ))
+        || check_overload!(@reachable_any
+            tag [$tag] ;
+            cur [$cur] ;
+            seen [$($seen),*] ;
+            combos [$($rest),*]
+        )
+    };
+
+    // A combo is unseen if no earlier arm's pattern matches it -- vacuously
+    // true before any arm has been seen.
+    (@not_seen tag [$tag:ident] ; combo [$($combo:ident),+] ; seen []) => {
+        true
+    };
+
+    (@not_seen tag [$tag:ident] ; combo [$($combo:ident),+] ; seen [$($seen:pat),+]) => {
+        !matches!(($($tag::$combo),+), $($seen)|+)
+    };
+}
+
+check_overload! {
+    #[allow(private_bounds)]
+    pub fn f<T1: AsTrait1Or2, T2: AsTrait1Or2>(x: T1, y: T2) -> FResult {
+        tag AllTraits { Trait1, Trait2 }
+        match (T1::TAG, T2::TAG) {
+            (AllTraits::Trait2, _) => FResult::Str(x.t2().method2()),
+            (_, AllTraits::Trait2) => FResult::Str(y.t2().method2()),
+            (AllTraits::Trait1, AllTraits::Trait1) => FResult::IntInt(
+                x.t1().method1(), y.t1().method1()),
+        }
     }
 }
 
@@ -87,6 +483,18 @@ enum XorTraits {
     Traits2And1,
 }
 
+/// `f_xor` only accepts a differing pair of trait wrappers: there is no
+/// arm for `(AsTrait1, AsTrait1)` or `(AsTrait2, AsTrait2)` because
+/// `PairAsTraits1Xor2` simply isn't implemented for same-trait pairs. The
+/// `on_unimplemented` message below replaces the generic "trait bound not
+/// satisfied" that would otherwise show up at the commented-out call
+/// sites in `main.rs` with one that names the actual gap.
+#[diagnostic::on_unimplemented(
+    message = "`f_xor` has no arm for the pair `{Self}`",
+    label = "this pair of trait wrappers has no `f_xor` arm",
+    note = "PairAsTraits1Xor2 is only implemented for (AsTrait1, AsTrait2) and (AsTrait2, AsTrait1); \
+            a same-trait pair like (AsTrait1, AsTrait1) is unhandled by design"
+)]
 trait PairAsTraits1Xor2 {
     const TRAITS: XorTraits;
 
@@ -145,3 +553,356 @@ pub fn f_xor<P: PairAsTraits1Xor2>(x_y: P) -> FXorResult {
         }
     }
 }
+
+/// Generalizes `PairAsTraits1Xor2` to tuples of arity 2 through 12: any
+/// `(A0, ..., An)` where every `Ai: AsTrait1Or2` gets `TAGS`, the array of
+/// each element's own tag, plus `t1`/`t2` accessors keyed by position.
+/// The accessors return `&dyn Trait*` rather than `&impl Trait*` because a
+/// single method has to serve every position, and positions hold
+/// different concrete `Ai` types.
+trait TupleAsTraits<const N: usize> {
+    const TAGS: [AllTraits; N];
+
+    fn t1(&self, index: usize) -> &dyn Trait1;
+    fn t2(&self, index: usize) -> &dyn Trait2;
+}
+
+macro_rules! count_idents {
+    () => { 0 };
+    ($head:ident $($tail:ident)*) => { 1 + count_idents!($($tail)*) };
+}
+
+macro_rules! impl_tuple_as_traits {
+    ($(($T:ident, $idx:tt)),+ $(,)?) => {
+        impl<$($T: AsTrait1Or2),+> TupleAsTraits<{ count_idents!($($T)+) }> for ($($T,)+) {
+            const TAGS: [AllTraits; count_idents!($($T)+)] = [$($T::TAG),+];
+
+            fn t1(&self, index: usize) -> &dyn Trait1 {
+                match index {
+                    $($idx => self.$idx.t1(),)+
+                    _ => panic!("TupleAsTraits::t1: index out of range"),
+                }
+            }
+
+            fn t2(&self, index: usize) -> &dyn Trait2 {
+                match index {
+                    $($idx => self.$idx.t2(),)+
+                    _ => panic!("TupleAsTraits::t2: index out of range"),
+                }
+            }
+        }
+    };
+}
+
+// Generates `impl_tuple_as_traits!` for every prefix of the (ident, index)
+// pairs below, i.e. for arities 2..=12. The index is carried alongside
+// each ident because a tuple field access (`self.0`, `self.1`, ...) needs
+// a literal position, which a macro can't derive from an arbitrary ident.
+macro_rules! impl_tuples_up_to {
+    ($first:tt, $second:tt $(, $rest:tt)*) => {
+        impl_tuple_as_traits!($first, $second);
+        impl_tuples_up_to!(@extend [$first, $second]; $($rest),*);
+    };
+    (@extend [$($acc:tt),+];) => {};
+    (@extend [$($acc:tt),+]; $next:tt $(, $rest:tt)*) => {
+        impl_tuple_as_traits!($($acc),+, $next);
+        impl_tuples_up_to!(@extend [$($acc),+, $next]; $($rest),*);
+    };
+}
+
+impl_tuples_up_to!(
+    (A0, 0), (A1, 1), (A2, 2), (A3, 3), (A4, 4), (A5, 5),
+    (A6, 6), (A7, 7), (A8, 8), (A9, 9), (A10, 10), (A11, 11)
+);
+
+#[derive(PartialEq)]
+pub enum F3Result {
+    Str(String),
+    IntIntInt(u32, u32, u32),
+}
+
+#[allow(private_bounds)]
+pub fn f3<T0, T1, T2>(x: (T0, T1, T2)) -> F3Result
+where
+    T0: AsTrait1Or2,
+    T1: AsTrait1Or2,
+    T2: AsTrait1Or2,
+{
+    // NOTE: It's important to ALWAYS check for exhaustiveness.
+    match <(T0, T1, T2) as TupleAsTraits<3>>::TAGS {
+        [AllTraits::Trait2, _, _] => F3Result::Str(x.t2(0).method2()),
+        [_, AllTraits::Trait2, _] => F3Result::Str(x.t2(1).method2()),
+        [_, _, AllTraits::Trait2] => F3Result::Str(x.t2(2).method2()),
+        [AllTraits::Trait1, AllTraits::Trait1, AllTraits::Trait1] => F3Result::IntIntInt(
+            x.t1(0).method1(), x.t1(1).method1(), x.t1(2).method1()),
+    }
+}
+
+// A wrapper still commits a value to exactly one trait above (`AsTrait1` =>
+// `AllTraits::Trait1`), so a type implementing both traits can only ever be
+// passed in as one of them. `AsTraitsBitset` replaces the scalar tag with a
+// `TRAITS: u32` bitmask: bit *k* set means "this value can be viewed as
+// trait *k + 1*" (`TRAIT1_BIT` is bit 0, `TRAIT2_BIT` is bit 1).
+// `AsTrait1`/`AsTrait2` still set a single bit each; the new
+// `AsTraits1And2` wrapper (built with the `AsTraits!` constructor) sets both.
+
+const TRAIT1_BIT: u32 = 1 << 0;
+const TRAIT2_BIT: u32 = 1 << 1;
+
+trait AsTraitsBitset {
+    const TRAITS: u32;
+    fn t1(&self) -> &impl Trait1;
+    fn t2(&self) -> &impl Trait2;
+}
+
+impl<T: Trait1> AsTraitsBitset for AsTrait1<'_, T> {
+    const TRAITS: u32 = TRAIT1_BIT;
+
+    fn t1(&self) -> &impl Trait1 {
+        self.0
+    }
+
+    fn t2(&self) -> &impl Trait2 {
+        &NO_IMPL_TRAIT2
+    }
+}
+
+impl<T: Trait2> AsTraitsBitset for AsTrait2<'_, T> {
+    const TRAITS: u32 = TRAIT2_BIT;
+
+    fn t1(&self) -> &impl Trait1 {
+        &NO_IMPL_TRAIT1
+    }
+
+    fn t2(&self) -> &impl Trait2 {
+        self.0
+    }
+}
+
+/// A value that can be viewed as both `Trait1` and `Trait2` at once.
+/// Build one with the `AsTraits!` constructor rather than directly.
+#[derive(Clone, Copy)]
+pub struct AsTraits1And2<'a, T: Trait1 + Trait2>(pub &'a T);
+
+impl<T: Trait1 + Trait2> AsTraitsBitset for AsTraits1And2<'_, T> {
+    const TRAITS: u32 = TRAIT1_BIT | TRAIT2_BIT;
+
+    fn t1(&self) -> &impl Trait1 {
+        self.0
+    }
+
+    fn t2(&self) -> &impl Trait2 {
+        self.0
+    }
+}
+
+/// For a `T: Trait1 + Trait2`, wraps `$val` so it sets both trait bits
+/// instead of committing to just one of `AsTrait1`/`AsTrait2`.
+macro_rules! AsTraits {
+    ($val:expr) => {
+        $crate::overloading::AsTraits1And2($val)
+    };
+}
+
+pub(crate) use AsTraits;
+
+/// Because a value can now set more than one bit, more than one overload
+/// arm may apply to a given pair of arguments. `winner` below computes the
+/// highest-scoring applicable arm exactly once -- both the match and the
+/// ambiguity check branch on that single result, so a spec tweak to one
+/// arm can't drift out of sync with the other.
+#[allow(private_bounds)]
+pub fn f_bits<T1: AsTraitsBitset, T2: AsTraitsBitset>(x: T1, y: T2) -> FResult {
+    const ARM_BOTH_TRAIT1: usize = 0; // pins x and y to Trait1
+    const ARM_Y_TRAIT2: usize = 1; // pins y to Trait2
+    const ARM_X_TRAIT2: usize = 2; // pins x to Trait2
+    const ARMS: [usize; 3] = [ARM_BOTH_TRAIT1, ARM_Y_TRAIT2, ARM_X_TRAIT2];
+
+    const fn applicable(x_traits: u32, y_traits: u32, arm: usize) -> bool {
+        match arm {
+            ARM_BOTH_TRAIT1 => x_traits & TRAIT1_BIT != 0 && y_traits & TRAIT1_BIT != 0,
+            ARM_Y_TRAIT2 => y_traits & TRAIT2_BIT != 0,
+            ARM_X_TRAIT2 => x_traits & TRAIT2_BIT != 0,
+            _ => false,
+        }
+    }
+
+    const fn specificity(arm: usize) -> u32 {
+        match arm {
+            ARM_BOTH_TRAIT1 => 2,
+            _ => 1,
+        }
+    }
+
+    // Picks the highest-scoring applicable arm; panics at compile time if
+    // none applies (every `AsTraitsBitset` must cover at least one trait)
+    // or if two applicable arms tie at the highest score.
+    const fn winner(x_traits: u32, y_traits: u32) -> usize {
+        let mut best: Option<usize> = None;
+        let mut best_spec = 0;
+        let mut ties = 0;
+        let mut i = 0;
+        while i < ARMS.len() {
+            let arm = ARMS[i];
+            if applicable(x_traits, y_traits, arm) {
+                let spec = specificity(arm);
+                if spec > best_spec {
+                    best_spec = spec;
+                    best = Some(arm);
+                    ties = 1;
+                } else if spec == best_spec {
+                    ties += 1;
+                }
+            }
+            i += 1;
+        }
+        if ties > 1 {
+            panic!("ambiguous overload: more than one arm ties at the highest specificity");
+        }
+        match best {
+            Some(arm) => arm,
+            None => panic!("no applicable arm: AsTraitsBitset must always cover at least one trait"),
+        }
+    }
+
+    match const { winner(T1::TRAITS, T2::TRAITS) } {
+        ARM_BOTH_TRAIT1 => FResult::IntInt(x.t1().method1(), y.t1().method1()),
+        ARM_Y_TRAIT2 => FResult::Str(y.t2().method2()),
+        ARM_X_TRAIT2 => FResult::Str(x.t2().method2()),
+        _ => unreachable!("winner only ever returns one of the declared ARMS"),
+    }
+}
+
+// Dispatch so far has only ever been keyed on *which* trait a value can be
+// viewed as. Extend that to "which trait *and* a compile-time-evaluable
+// condition": a wrapper can now carry a `const COND: bool`, computed from
+// the wrapped type via the `GuardCond` marker trait below, alongside its
+// trait tag.
+
+/// Implemented by a wrapped type to give guarded dispatch a compile-time
+/// condition to test, in addition to its trait tag.
+pub trait GuardCond {
+    const COND: bool;
+}
+
+trait AsGuardedTrait {
+    const TAG: AllTraits;
+    const COND: bool;
+    fn t1(&self) -> &impl Trait1;
+    fn t2(&self) -> &impl Trait2;
+}
+
+#[derive(Clone, Copy)]
+pub struct AsTrait1Guarded<'a, T: Trait1 + GuardCond>(pub &'a T);
+
+#[derive(Clone, Copy)]
+pub struct AsTrait2Guarded<'a, T: Trait2 + GuardCond>(pub &'a T);
+
+impl<T: Trait1 + GuardCond> AsGuardedTrait for AsTrait1Guarded<'_, T> {
+    const TAG: AllTraits = AllTraits::Trait1;
+    const COND: bool = T::COND;
+
+    fn t1(&self) -> &impl Trait1 {
+        self.0
+    }
+
+    fn t2(&self) -> &impl Trait2 {
+        &NO_IMPL_TRAIT2
+    }
+}
+
+impl<T: Trait2 + GuardCond> AsGuardedTrait for AsTrait2Guarded<'_, T> {
+    const TAG: AllTraits = AllTraits::Trait2;
+    const COND: bool = T::COND;
+
+    fn t1(&self) -> &impl Trait1 {
+        &NO_IMPL_TRAIT1
+    }
+
+    fn t2(&self) -> &impl Trait2 {
+        self.0
+    }
+}
+
+/// One candidate arm of a guarded dispatch: the trait tag it's keyed on
+/// (`None` matches any tag), whether it additionally requires the guard
+/// to hold, and the handler it selects when it wins.
+struct Candidate {
+    tag_pattern: Option<AllTraits>,
+    guard: bool,
+    handler_index: usize,
+}
+
+const fn tag_matches(pattern: &Option<AllTraits>, tag: &AllTraits) -> bool {
+    matches!(
+        (pattern, tag),
+        (None, _)
+            | (Some(AllTraits::Trait1), AllTraits::Trait1)
+            | (Some(AllTraits::Trait2), AllTraits::Trait2)
+    )
+}
+
+/// Resolves which candidate arm wins for a given tag and guard value.
+///
+/// Candidates are considered in priority order (index 0 first); the
+/// winner is the first one whose tag pattern matches *and*, if it
+/// requires the guard, whose guard holds. Everything here runs at
+/// monomorphization time, so a dispatch function built on `Selection`
+/// adds zero runtime branching beyond the final match on the resolved
+/// handler index.
+struct Selection;
+
+impl Selection {
+    const fn resolve<const N: usize>(tag: &AllTraits, cond: bool, candidates: &[Candidate; N]) -> usize {
+        let mut winner: Option<usize> = None;
+        let mut has_fallback = false;
+        let mut i = 0;
+        while i < N {
+            let candidate = &candidates[i];
+            if tag_matches(&candidate.tag_pattern, tag) {
+                if winner.is_none() && (!candidate.guard || cond) {
+                    winner = Some(candidate.handler_index);
+                }
+                if !candidate.guard {
+                    has_fallback = true;
+                }
+            }
+            i += 1;
+        }
+        // INVARIANT: a fallback (unguarded) arm must exist for every tag,
+        // or this panics at compile time instead of silently picking a
+        // wrong handler -- analogous to requiring an exhaustive match.
+        match (winner, has_fallback) {
+            (Some(handler_index), true) => handler_index,
+            _ => panic!("no guarded dispatch arm matched: a fallback (unguarded) arm must exist"),
+        }
+    }
+}
+
+#[derive(PartialEq)]
+pub enum GResult {
+    Big(u32),
+    Small(u32),
+    Str(String),
+}
+
+// Two arms share the `Trait1` tag: the guarded one only wins when the
+// wrapped type's `GuardCond::COND` holds, otherwise the unguarded arm
+// right after it is the fallback. `Trait2` has no guarded arm at all, so
+// its single candidate is unconditional.
+const G_CANDIDATES: [Candidate; 3] = [
+    Candidate { tag_pattern: Some(AllTraits::Trait1), guard: true, handler_index: 0 },
+    Candidate { tag_pattern: Some(AllTraits::Trait1), guard: false, handler_index: 1 },
+    Candidate { tag_pattern: Some(AllTraits::Trait2), guard: false, handler_index: 2 },
+];
+
+#[allow(private_bounds)]
+pub fn g<T: AsGuardedTrait>(x: T) -> GResult {
+    let winner = const { Selection::resolve(&T::TAG, T::COND, &G_CANDIDATES) };
+    match winner {
+        0 => GResult::Big(x.t1().method1()),
+        1 => GResult::Small(x.t1().method1()),
+        2 => GResult::Str(x.t2().method2()),
+        _ => unreachable!("Selection::resolve only ever returns a valid candidate index"),
+    }
+}